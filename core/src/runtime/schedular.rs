@@ -1,9 +1,15 @@
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
     future::Future,
+    hash::Hash,
+    marker::PhantomData,
     mem::offset_of,
     pin::Pin,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 
@@ -38,15 +44,156 @@ pub enum SchedularPoll {
     PendingProgress,
 }
 
-pub struct Schedular {
+/// The default value of a [`Schedular`]'s poll budget, i.e. the number of tasks driven per
+/// [`Schedular::poll`] call before yielding back to the root executor.
+///
+/// See [`Schedular::set_poll_budget`].
+const DEFAULT_POLL_BUDGET: usize = 128;
+
+/// A stable identifier of a task spawned on a [`Schedular`], yielded by [`Schedular::iter`].
+///
+/// Two `TaskId`s compare equal only while both refer to the same still-spawned task; once a
+/// task completes or is cancelled its id may be reused by a later task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// The state of a task as observed by [`Schedular::iter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// The task is in the schedular's run queue, waiting to be driven again.
+    Queued,
+    /// The task is currently being driven. Because the schedular supports reentrant polling
+    /// (see `reentrant`/`popped_running` in [`Schedular::poll`]), this can be observed on a task
+    /// other than the one calling `iter`.
+    Running,
+    /// The task is suspended on an external waker (e.g. a timer or I/O event) and is not
+    /// currently anywhere in the schedular's run queue. This is the common "stuck" case.
+    Waiting,
+    /// The task has finished and is only waiting to be detached from the all task list.
+    Done,
+}
+
+/// Metadata about a single task spawned on a [`Schedular`], yielded by [`Schedular::iter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub state: TaskState,
+}
+
+/// An iterator over [`TaskInfo`] for every task spawned on a [`Schedular`], at the moment
+/// [`Schedular::iter`] was called.
+///
+/// `iter` walks the all task list eagerly, up front, into an owned snapshot rather than handing
+/// back a lazy walk over live task pointers. A lazy walk would be unsound to expose through a
+/// safe iterator: the schedular supports reentrant polling, so a future being driven can call
+/// back into the very schedular it is spawned on (e.g. to `cancel` another task) while holding
+/// an `Iter` returned earlier in the same call stack, which could otherwise observe the all task
+/// list mid-mutation or outlive a task it still points to.
+pub struct Iter<'a, K, O = ()> {
+    marker: PhantomData<&'a Schedular<K, O>>,
+    tasks: std::vec::IntoIter<TaskInfo>,
+}
+
+impl<K, O> Iterator for Iter<'_, K, O> {
+    type Item = TaskInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tasks.next()
+    }
+}
+
+/// A cheap, cloneable, thread-safe handle that cancels the future spawned together with it via
+/// [`Schedular::push_abortable`].
+///
+/// Unlike [`Schedular::cancel`], which looks a task up by an embedder-chosen key, an
+/// `AbortHandle` is returned directly from the spawn call and can be handed to external I/O
+/// callbacks to cancel a specific job mid-flight, e.g. when an HTTP server cancels a request
+/// whose handler is still awaiting a JS promise.
+#[derive(Clone)]
+pub struct AbortHandle {
+    aborted: Arc<AtomicBool>,
+    waker: Arc<atomic_waker::AtomicWaker>,
+}
+
+impl AbortHandle {
+    /// Abort the associated task.
+    ///
+    /// If the task has not completed yet its future is dropped the next time the schedular polls
+    /// it, without being polled itself again. Calling this more than once, or after the task has
+    /// already completed on its own, is a no-op. Safe to call from any thread.
+    pub fn abort(&self) {
+        self.aborted.store(true, Ordering::Release);
+        self.waker.wake();
+    }
+}
+
+/// Wraps a future spawned via [`Schedular::push_abortable`], checking the shared `aborted` flag
+/// before every poll of the inner future so that aborting drops it without driving it again.
+struct AbortableFuture<F> {
+    future: F,
+    aborted: Arc<AtomicBool>,
+    waker: Arc<atomic_waker::AtomicWaker>,
+}
+
+impl<F> Future for AbortableFuture<F>
+where
+    F: Future<Output = ()>,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Register the waker *before* checking `aborted`. `AbortHandle::abort` can run on
+        // another thread between a check and a later registration; if we checked first an
+        // `abort()` landing in that window would set the flag and wake a waker we had not
+        // registered yet, i.e. a lost wakeup that leaves this future parked forever. Registering
+        // first closes that window, mirroring the check-register-recheck pattern used by
+        // `Schedular::poll_next`.
+        self.waker.register(cx.waker());
+        if self.aborted.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+
+        // Safety: `future` is never moved out of `self`, only pinned-projected to.
+        let future = unsafe { self.map_unchecked_mut(|s| &mut s.future) };
+        future.poll(cx)
+    }
+}
+
+/// The schedular, generic over `K`, the key used to individually reference a spawned task, and
+/// `O`, the output type collected from futures spawned with [`Schedular::push_with_output`].
+///
+/// Most embedders never need either and can leave `K` and `O` at their defaults of `()`. Pass a
+/// concrete key type to [`Schedular::push_keyed`] to later [`Schedular::cancel`] a single pending
+/// future, e.g. a timer or fetch job, without tearing down every other in-flight task. Pass a
+/// concrete output type to [`Schedular::push_with_output`] to collect its result through
+/// [`Schedular::poll_next`] instead of discarding it.
+pub struct Schedular<K = (), O = ()> {
     len: Cell<usize>,
     reentrant: Cell<usize>,
     should_poll: Arc<Queue>,
     all_next: Cell<Option<ErasedTaskPtr>>,
     all_prev: Cell<Option<ErasedTaskPtr>>,
+    keyed: RefCell<HashMap<K, ErasedTaskPtr>>,
+    keyed_rev: RefCell<HashMap<usize, K>>,
+    /// Addresses of tasks that were [`Schedular::cancel`]led while still `running`, i.e. from
+    /// within their own future (directly, or via a [`Schedular::push_keyed`] collision). Detached
+    /// and dropped by [`Schedular::poll`] as soon as the task stops running instead of
+    /// immediately, since `poll` still owns the task further up its own call stack at that point.
+    pending_cancels: RefCell<HashSet<usize>>,
+    poll_budget: Cell<usize>,
+    completed: RefCell<VecDeque<O>>,
+    completed_waker: atomic_waker::AtomicWaker,
+    /// Number of futures spawned via [`Schedular::push_with_output`] that have not yet produced
+    /// their output. Tracked separately from `len` so that [`Schedular::poll_next`] only reports
+    /// end-of-stream once every *output* task is done, regardless of how many plain `push`,
+    /// `push_keyed` or `push_abortable` tasks are still spawned alongside them.
+    pending_outputs: Cell<usize>,
 }
 
-impl Schedular {
+impl<K, O> Schedular<K, O>
+where
+    K: Hash + Eq,
+{
     /// Create a new schedular.
     pub fn new() -> Self {
         let queue = Arc::new(Queue::new());
@@ -59,6 +206,13 @@ impl Schedular {
             should_poll: queue,
             all_prev: Cell::new(None),
             all_next: Cell::new(None),
+            keyed: RefCell::new(HashMap::new()),
+            keyed_rev: RefCell::new(HashMap::new()),
+            pending_cancels: RefCell::new(HashSet::new()),
+            poll_budget: Cell::new(DEFAULT_POLL_BUDGET),
+            completed: RefCell::new(VecDeque::new()),
+            completed_waker: atomic_waker::AtomicWaker::new(),
+            pending_outputs: Cell::new(0),
         }
     }
 
@@ -67,10 +221,83 @@ impl Schedular {
         self.all_next.get().is_none()
     }
 
+    /// Returns the number of tasks currently spawned on this schedular.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Set the maximum number of futures driven per call to [`Schedular::poll`] before it
+    /// returns [`SchedularPoll::ShouldYield`], regardless of how many tasks are currently
+    /// spawned.
+    ///
+    /// A small number of futures that wake themselves immediately and keep returning `Pending`
+    /// would otherwise monopolize a single `poll` call and starve the root executor; this budget
+    /// bounds that. Defaults to [`DEFAULT_POLL_BUDGET`]; tune it up for throughput or down for
+    /// latency depending on the embedder's workload.
+    pub fn set_poll_budget(&self, budget: usize) {
+        self.poll_budget.set(budget);
+    }
+
+    /// Returns an iterator over a snapshot of metadata for every task currently spawned on this
+    /// schedular.
+    ///
+    /// This walks the all task list once, up front, yielding a [`TaskInfo`] per task without
+    /// touching the erased future itself, so it is safe to call from within a running future and
+    /// cannot trigger re-entrant polling. Because the snapshot is collected eagerly, it does not
+    /// reflect tasks spawned, completed or cancelled after `iter` returns. Useful for embedders
+    /// building debuggers or metrics dashboards that need to report how many jobs are pending and
+    /// which ones are stuck.
+    pub fn iter(&self) -> Iter<'_, K, O> {
+        let mut tasks = Vec::with_capacity(self.len.get());
+        let mut cur = self.all_next.get();
+        while let Some(task) = cur {
+            // Safety: `task` is a pointer still owned by the all task list, which this loop
+            // keeps alive by borrowing `self` for its entire, uninterrupted duration -- the walk
+            // completes before control returns to the caller, so it is sound even if the caller
+            // goes on to mutate the schedular from within the same future that called `iter`.
+            let (next, state) = unsafe {
+                let next = task.body().next.get();
+                let state = if task.body().done.get() {
+                    TaskState::Done
+                } else if task.body().running.get() {
+                    TaskState::Running
+                } else if task.body().queued.load(Ordering::Relaxed) {
+                    TaskState::Queued
+                } else {
+                    TaskState::Waiting
+                };
+                (next, state)
+            };
+            tasks.push(TaskInfo {
+                id: TaskId(Self::task_addr(task)),
+                state,
+            });
+            cur = next;
+        }
+        Iter {
+            marker: PhantomData,
+            tasks: tasks.into_iter(),
+        }
+    }
+
     /// # Safety
     /// This function erases any lifetime associated with the future.
     /// Caller must ensure that either the future completes or is dropped before the lifetime
     pub unsafe fn push<F>(&self, f: F)
+    where
+        F: Future<Output = ()>,
+    {
+        self.spawn(f);
+    }
+
+    /// Allocate a task for `f`, link it into the all task list and the run queue, and return
+    /// its pointer. Shared by [`Schedular::push`] and [`Schedular::push_keyed`] so the two
+    /// cannot drift out of sync with each other.
+    ///
+    /// # Safety
+    /// This function erases any lifetime associated with the future.
+    /// Caller must ensure that either the future completes or is dropped before the lifetime
+    unsafe fn spawn<F>(&self, f: F) -> ErasedTaskPtr
     where
         F: Future<Output = ()>,
     {
@@ -89,6 +316,154 @@ impl Schedular {
         let task_ptr = ErasedTask::into_ptr(task);
         Pin::new_unchecked(&*self.should_poll).push(task_ptr.as_node_ptr());
         self.len.set(self.len.get() + 1);
+
+        task_ptr
+    }
+
+    /// Like [`Schedular::push`], but associates the spawned future with `key` so that it can
+    /// later be individually cancelled with [`Schedular::cancel`], without affecting any other
+    /// spawned future.
+    ///
+    /// If a task is already spawned under `key`, it is cancelled and dropped first, mirroring
+    /// the way inserting into the `keyed` map overwrites the key's old value.
+    ///
+    /// # Safety
+    /// This function erases any lifetime associated with the future.
+    /// Caller must ensure that either the future completes or is dropped before the lifetime
+    pub unsafe fn push_keyed<F>(&self, key: K, f: F)
+    where
+        F: Future<Output = ()>,
+        K: Clone,
+    {
+        // Cancel whatever was previously spawned under this key first. Without this, the old
+        // `keyed` entry would simply be overwritten while its address stayed behind in
+        // `keyed_rev`, so the *old* task's natural completion would later erase the *new*
+        // task's still-live `keyed` entry, leaving it uncancellable.
+        self.cancel(&key);
+
+        let task_ptr = self.spawn(f);
+        self.keyed.borrow_mut().insert(key.clone(), task_ptr);
+        self.keyed_rev.borrow_mut().insert(Self::task_addr(task_ptr), key);
+    }
+
+    /// Cancel and drop the future previously spawned via [`Schedular::push_keyed`] under `key`.
+    ///
+    /// Returns `true` if a task was found for `key`, `false` if no such task is currently
+    /// spawned, e.g. because it already completed or was already cancelled.
+    pub fn cancel(&self, key: &K) -> bool {
+        let Some(task_ptr) = self.keyed.borrow_mut().remove(key) else {
+            return false;
+        };
+        self.keyed_rev.borrow_mut().remove(&Self::task_addr(task_ptr));
+
+        // Safety: `task_ptr` was taken from `self.keyed` which only ever holds pointers to tasks
+        // still owned by the all task list, so it is valid to inspect and, once it is safe to do
+        // so, detach and drop here.
+        unsafe {
+            if task_ptr.body().running.get() {
+                // `task_ptr` is currently being driven further up the call stack -- it is
+                // cancelling its own key, directly or via a `push_keyed` collision, from within
+                // its own future. `Schedular::poll` still owns it through that frame, so
+                // detaching and dropping it here would pull it out from under that frame. Defer
+                // the actual removal to `poll`, which checks `pending_cancels` as soon as the
+                // task stops running.
+                self.pending_cancels.borrow_mut().insert(Self::task_addr(task_ptr));
+            } else {
+                self.pop_task_all(task_ptr);
+            }
+        }
+        true
+    }
+
+    /// A stable address derived from a task pointer, used as the key of `keyed_rev` so a
+    /// completed or cancelled task can find its way back to the matching entry in `keyed`
+    /// without requiring `K: Clone` on every lookup.
+    fn task_addr(task_ptr: ErasedTaskPtr) -> usize {
+        task_ptr.as_node_ptr().as_ptr() as usize
+    }
+
+    /// Like [`Schedular::push`], but collects the future's output instead of discarding it.
+    ///
+    /// The result becomes available, in completion order, through [`Schedular::poll_next`],
+    /// turning the schedular into a `FuturesUnordered`-style result stream. This lets embedders
+    /// run a batch of JS-driven async jobs and consume their results as they finish instead of
+    /// threading an `Arc<Mutex<..>>` through every spawned closure.
+    ///
+    /// # Safety
+    /// This function erases any lifetime associated with the future.
+    /// Caller must ensure that either the future completes or is dropped before the lifetime
+    pub unsafe fn push_with_output<F>(&self, f: F)
+    where
+        F: Future<Output = O>,
+    {
+        let this = self as *const Self;
+        self.pending_outputs.set(self.pending_outputs.get() + 1);
+        self.push(async move {
+            let output = f.await;
+            // Safety: `self` outlives the wrapped future per `push`'s own safety contract, and
+            // is only ever accessed here through the `&self` methods below.
+            unsafe {
+                (*this).completed.borrow_mut().push_back(output);
+                (*this).pending_outputs.set((*this).pending_outputs.get() - 1);
+                (*this).completed_waker.wake();
+            }
+        });
+    }
+
+    /// Drain the outputs of futures spawned via [`Schedular::push_with_output`], in the order
+    /// they completed.
+    ///
+    /// Returns `Poll::Ready(None)` once every future spawned via `push_with_output` has produced
+    /// its output and no buffered output is left, mirroring a stream's end-of-stream signal. This
+    /// is tracked independently of the schedular as a whole, so plain `push`, `push_keyed` or
+    /// `push_abortable` tasks spawned alongside them do not keep `poll_next` pending forever, nor
+    /// does their completion cause it to return `None` early.
+    pub fn poll_next(&self, cx: &mut Context) -> Poll<Option<O>> {
+        if let Some(output) = self.completed.borrow_mut().pop_front() {
+            return Poll::Ready(Some(output));
+        }
+        if self.pending_outputs.get() == 0 {
+            return Poll::Ready(None);
+        }
+        self.completed_waker.register(cx.waker());
+        // Re-check after registering in case a task completed between the first check above and
+        // the waker registration.
+        if let Some(output) = self.completed.borrow_mut().pop_front() {
+            return Poll::Ready(Some(output));
+        }
+        Poll::Pending
+    }
+
+    /// Like [`Schedular::push`], but returns an [`AbortHandle`] that can cancel just this one
+    /// future from anywhere, including another thread, without tearing down every other
+    /// in-flight task.
+    ///
+    /// Unlike [`Schedular::push_keyed`] and [`Schedular::cancel`], which cancel by an
+    /// embedder-chosen key, this hands back a ready-made token at spawn time, which is what
+    /// makes it a good fit for passing into external I/O callbacks.
+    ///
+    /// # Safety
+    /// This function erases any lifetime associated with the future.
+    /// Caller must ensure that either the future completes or is dropped before the lifetime
+    pub unsafe fn push_abortable<F>(&self, f: F) -> AbortHandle
+    where
+        F: Future<Output = ()>,
+    {
+        let aborted = Arc::new(AtomicBool::new(false));
+        let waker = Arc::new(atomic_waker::AtomicWaker::new());
+
+        let handle = AbortHandle {
+            aborted: aborted.clone(),
+            waker: waker.clone(),
+        };
+
+        self.push(AbortableFuture {
+            future: f,
+            aborted,
+            waker,
+        });
+
+        handle
     }
 
     /// Add a new task to the all task list.
@@ -220,9 +595,27 @@ impl Schedular {
             cur_ptr.body().running.set(false);
             self.reentrant.set(self.reentrant.get() - 1);
 
+            // The task may have cancelled its own key while it was running, which `cancel`
+            // could not act on immediately since this frame still owned it. Do so now that it
+            // no longer is, regardless of whether it polled to `Ready` or `Pending`.
+            let force_remove = self
+                .pending_cancels
+                .borrow_mut()
+                .remove(&Self::task_addr(cur_ptr));
+
             match res {
                 Poll::Ready(_) => {
-                    // Nothing todo the defer will remove the task from the list.
+                    // The defer will remove the task from the all list, but if it was spawned
+                    // via `push_keyed` its entry in the keyed maps would otherwise linger
+                    // forever, so clear it here too.
+                    let addr = Self::task_addr(cur_ptr);
+                    if let Some(key) = self.keyed_rev.borrow_mut().remove(&addr) {
+                        self.keyed.borrow_mut().remove(&key);
+                    }
+                }
+                Poll::Pending if force_remove => {
+                    // Cancelled itself while running: let the `remove` defer run as if the task
+                    // had completed, instead of keeping it alive in the list.
                 }
                 Poll::Pending => {
                     cur_ptr.body().running.set(false);
@@ -234,15 +627,23 @@ impl Schedular {
                     // again.
                     yielded += cur_ptr.body().queued.load(Ordering::Relaxed) as usize;
 
-                    // If we polled all the futures atleas once,
-                    // or more then one future immediatily queued itself after being polled,
+                    // If more than two futures immediatily queued themselves after being polled,
                     // yield back to the parent schedular.
-                    if yielded > 2 || iteration > self.len.get() {
+                    if yielded > 2 {
                         cx.waker().wake_by_ref();
                         return SchedularPoll::ShouldYield;
                     }
                 }
             }
+
+            // Bound the number of futures driven per call regardless of whether they kept
+            // completing (`Ready`) or kept returning `Pending`: a run of tasks that finish one
+            // after another, e.g. each spawning the next, should yield back to the root executor
+            // just as readily as a run of tasks that keep re-queuing themselves.
+            if iteration >= self.poll_budget.get() {
+                cx.waker().wake_by_ref();
+                return SchedularPoll::ShouldYield;
+            }
         }
     }
 
@@ -252,6 +653,12 @@ impl Schedular {
         while let Some(c) = self.all_next.get() {
             unsafe { self.pop_task_all(c) }
         }
+        self.keyed.borrow_mut().clear();
+        self.keyed_rev.borrow_mut().clear();
+        self.pending_cancels.borrow_mut().clear();
+        // Tasks dropped here never reach the `push_with_output` wrapper's completion, so their
+        // contribution to `pending_outputs` would otherwise never be decremented.
+        self.pending_outputs.set(0);
 
         loop {
             let cur = match unsafe { Pin::new_unchecked(&*self.should_poll).pop() } {
@@ -268,8 +675,279 @@ impl Schedular {
     }
 }
 
-impl Drop for Schedular {
+impl<K, O> Drop for Schedular<K, O>
+where
+    K: Hash + Eq,
+{
     fn drop(&mut self) {
         self.clear()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+        task::{RawWaker, RawWakerVTable, Waker},
+    };
+
+    use super::*;
+
+    fn noop_waker() -> Waker {
+        fn clone(_data: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_data: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    /// A future that stays `Pending` until `ready` is set, capturing the last waker it was
+    /// polled with so a test can simulate an external event (e.g. a timer firing) later.
+    struct ManualFuture {
+        ready: Rc<Cell<bool>>,
+        waker: Rc<RefCell<Option<Waker>>>,
+    }
+
+    impl Future for ManualFuture {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            *self.waker.borrow_mut() = Some(cx.waker().clone());
+            if self.ready.get() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn iter_distinguishes_waiting_from_queued() {
+        let sched = Schedular::<()>::new();
+        let ready = Rc::new(Cell::new(false));
+        let waker_slot = Rc::new(RefCell::new(None));
+
+        unsafe {
+            sched.push(ManualFuture {
+                ready: ready.clone(),
+                waker: waker_slot.clone(),
+            });
+        }
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+
+        // Drive once: the future returns `Pending` and nothing re-queues it, so it is genuinely
+        // waiting on an external waker, not about to be polled again.
+        unsafe { sched.poll(&mut cx) };
+        assert_eq!(sched.iter().next().unwrap().state, TaskState::Waiting);
+
+        // Simulate the external event (e.g. a timer) firing.
+        waker_slot.borrow().as_ref().unwrap().wake_by_ref();
+        assert_eq!(sched.iter().next().unwrap().state, TaskState::Queued);
+
+        // Let it finish.
+        ready.set(true);
+        unsafe { sched.poll(&mut cx) };
+        assert!(sched.iter().next().is_none());
+    }
+
+    #[test]
+    fn abort_before_first_poll_resolves_without_driving_the_future() {
+        let sched = Schedular::<()>::new();
+        let polled = Rc::new(Cell::new(0));
+
+        struct CountPolls(Rc<Cell<usize>>);
+        impl Future for CountPolls {
+            type Output = ();
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                self.0.set(self.0.get() + 1);
+                Poll::Pending
+            }
+        }
+
+        let handle = unsafe { sched.push_abortable(CountPolls(polled.clone())) };
+        // Abort lands before the schedular ever gets a chance to poll the future.
+        handle.abort();
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+        unsafe { sched.poll(&mut cx) };
+
+        assert_eq!(polled.get(), 0);
+        assert_eq!(sched.len(), 0);
+    }
+
+    #[test]
+    fn iter_is_an_eager_snapshot_not_a_live_walk() {
+        let sched = Schedular::<&'static str>::new();
+        unsafe {
+            sched.push_keyed(
+                "a",
+                ManualFuture {
+                    ready: Rc::new(Cell::new(false)),
+                    waker: Rc::new(RefCell::new(None)),
+                },
+            );
+            sched.push_keyed(
+                "b",
+                ManualFuture {
+                    ready: Rc::new(Cell::new(false)),
+                    waker: Rc::new(RefCell::new(None)),
+                },
+            );
+        }
+
+        let iter = sched.iter();
+        // Mutating the schedular after `iter` has already returned must not be visible through
+        // the snapshot it already collected.
+        sched.cancel(&"a");
+
+        assert_eq!(iter.count(), 2);
+    }
+
+    #[test]
+    fn poll_next_tracks_output_tasks_not_the_whole_schedular() {
+        let sched = Schedular::<(), i32>::new();
+        let ready = Rc::new(Cell::new(false));
+        let waker_slot = Rc::new(RefCell::new(None));
+
+        // A plain task with no output, spawned alongside the output-producing one, that never
+        // finishes on its own.
+        unsafe {
+            sched.push(ManualFuture {
+                ready: ready.clone(),
+                waker: waker_slot.clone(),
+            });
+        }
+        unsafe {
+            sched.push_with_output(async { 42 });
+        }
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+        unsafe { sched.poll(&mut cx) };
+
+        assert_eq!(sched.poll_next(&mut cx), Poll::Ready(Some(42)));
+        // The only output-producing task is done; the plain task that is still spawned must not
+        // keep the output stream pending.
+        assert_eq!(sched.poll_next(&mut cx), Poll::Ready(None));
+
+        ready.set(true);
+        unsafe { sched.poll(&mut cx) };
+    }
+
+    #[test]
+    fn poll_budget_bounds_a_run_of_immediately_completing_tasks() {
+        struct Immediate;
+        impl Future for Immediate {
+            type Output = ();
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                Poll::Ready(())
+            }
+        }
+
+        let sched = Schedular::<()>::new();
+        sched.set_poll_budget(1);
+        unsafe {
+            sched.push(Immediate);
+            sched.push(Immediate);
+        }
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+
+        // Both tasks are immediately ready, but the budget of 1 must still force a yield after
+        // driving only one of them, same as it would for a run of `Pending` tasks.
+        assert_eq!(unsafe { sched.poll(&mut cx) }, SchedularPoll::ShouldYield);
+        assert_eq!(sched.len(), 1);
+    }
+
+    #[test]
+    fn cancel_defers_removal_of_a_task_cancelling_its_own_key() {
+        struct SelfCancelling {
+            sched: *const Schedular<&'static str>,
+            key: &'static str,
+            polls: Rc<Cell<usize>>,
+        }
+
+        impl Future for SelfCancelling {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+                self.polls.set(self.polls.get() + 1);
+                // Safety: `sched` outlives this future, which never escapes the single `poll`
+                // call below in this test.
+                unsafe { (*self.sched).cancel(&self.key) };
+                Poll::Pending
+            }
+        }
+
+        let sched = Schedular::<&'static str>::new();
+        let polls = Rc::new(Cell::new(0));
+        let sched_ptr: *const Schedular<&'static str> = &sched;
+
+        unsafe {
+            sched.push_keyed(
+                "self",
+                SelfCancelling {
+                    sched: sched_ptr,
+                    key: "self",
+                    polls: polls.clone(),
+                },
+            );
+        }
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+        unsafe { sched.poll(&mut cx) };
+
+        assert_eq!(polls.get(), 1);
+        assert_eq!(sched.len(), 0);
+    }
+
+    #[test]
+    fn push_keyed_replaces_and_cancels_previous_task_on_key_collision() {
+        let sched = Schedular::<&'static str>::new();
+        let ready1 = Rc::new(Cell::new(false));
+        let waker1 = Rc::new(RefCell::new(None));
+        let ready2 = Rc::new(Cell::new(false));
+        let waker2 = Rc::new(RefCell::new(None));
+
+        unsafe {
+            sched.push_keyed(
+                "job",
+                ManualFuture {
+                    ready: ready1,
+                    waker: waker1,
+                },
+            );
+        }
+
+        let root_waker = noop_waker();
+        let mut cx = Context::from_waker(&root_waker);
+        unsafe { sched.poll(&mut cx) };
+
+        // Spawn a second task under the same, still-live key.
+        unsafe {
+            sched.push_keyed(
+                "job",
+                ManualFuture {
+                    ready: ready2,
+                    waker: waker2,
+                },
+            );
+        }
+        unsafe { sched.poll(&mut cx) };
+
+        // The first task must have been cancelled and dropped, leaving only the second.
+        assert_eq!(sched.len(), 1);
+
+        // Cancelling by key must cancel the *second*, currently live task, not silently no-op
+        // because its bookkeeping was clobbered when the first task was cleaned up.
+        assert!(sched.cancel(&"job"));
+        assert_eq!(sched.len(), 0);
+    }
+}